@@ -0,0 +1,46 @@
+use content::sorting::SortBy;
+
+/// A user-defined taxonomy, eg. tags or categories, declared in `config.toml` as:
+///
+/// ```toml
+/// [[taxonomies]]
+/// name = "tags"
+/// sort_by = "date"
+/// paginate_by = 20
+/// rss = true
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Taxonomy {
+    /// Used in the URLs and in the template lookup, eg `tags` for `/tags/rust`
+    pub name: String,
+    /// How to sort the pages belonging to each term
+    #[serde(default)]
+    pub sort_by: SortBy,
+    /// Generates `{name}/{term}/`, `{name}/{term}/page/2/`, ... when set
+    pub paginate_by: Option<usize>,
+    /// Generates a `{name}/{term}/atom.xml` feed for each term when `true`
+    #[serde(default)]
+    pub rss: bool,
+    /// Appends a disambiguating suffix to a term's slug instead of erroring out
+    /// when two terms collide
+    #[serde(default)]
+    pub rename_conflicting_slugs: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Config {
+    pub base_url: String,
+    pub default_language: String,
+    pub rss_limit: usize,
+    pub taxonomies: Vec<Taxonomy>,
+}
+
+impl Config {
+    pub fn make_permalink(&self, path: &str) -> String {
+        let trimmed = path.trim_matches('/');
+        if trimmed.is_empty() {
+            return format!("{}/", self.base_url.trim_right_matches('/'));
+        }
+        format!("{}/{}/", self.base_url.trim_right_matches('/'), trimmed)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod page;
+pub mod pagination;
+pub mod sorting;
+pub mod taxonomies;
+
+pub use self::page::Page;
+pub use self::taxonomies::{Taxonomy, TaxonomyItem};
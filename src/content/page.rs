@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+
+/// The metadata a page can declare in its front matter.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageFrontMatter {
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub weight: Option<usize>,
+    /// Taxonomy name -> terms this page belongs to, eg. `{"tags": ["rust", "web"]}`
+    #[serde(default)]
+    pub taxonomies: HashMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Page {
+    pub file_path: String,
+    pub permalink: String,
+    pub meta: PageFrontMatter,
+}
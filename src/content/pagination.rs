@@ -0,0 +1,115 @@
+use config::Config;
+use content::Page;
+
+/// One page of a paginated listing.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Pager<'a> {
+    pub current_index: usize,
+    pub number_of_pages: usize,
+    pub pages: Vec<&'a Page>,
+    pub permalink: String,
+    pub path: String,
+    pub previous: Option<String>,
+    pub next: Option<String>,
+}
+
+/// Splits a list of pages into `Pager`s of at most `paginate_by` entries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Paginator<'a> {
+    pub paginate_by: usize,
+    pub pagers: Vec<Pager<'a>>,
+}
+
+impl<'a> Paginator<'a> {
+    /// Builds one `Pager` per `paginate_by` pages, rooted at `base_path`
+    /// (eg. `tags/rust` -> `tags/rust/`, `tags/rust/page/2/`, ...).
+    pub fn from_pages(pages: &[&'a Page], paginate_by: usize, base_path: &str, config: &Config) -> Paginator<'a> {
+        let chunks: Vec<_> = pages.chunks(paginate_by.max(1)).collect();
+        let number_of_pages = chunks.len();
+
+        let mut pagers: Vec<Pager> = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                let current_index = i + 1;
+                let path = if current_index == 1 {
+                    format!("{}/", base_path)
+                } else {
+                    format!("{}/page/{}/", base_path, current_index)
+                };
+
+                Pager {
+                    current_index,
+                    number_of_pages,
+                    pages: chunk.to_vec(),
+                    permalink: config.make_permalink(&path),
+                    path,
+                    previous: None,
+                    next: None,
+                }
+            })
+            .collect();
+
+        let permalinks: Vec<String> = pagers.iter().map(|p| p.permalink.clone()).collect();
+        for (i, pager) in pagers.iter_mut().enumerate() {
+            if i > 0 {
+                pager.previous = Some(permalinks[i - 1].clone());
+            }
+            if i + 1 < permalinks.len() {
+                pager.next = Some(permalinks[i + 1].clone());
+            }
+        }
+
+        Paginator { paginate_by, pagers }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::Config;
+    use content::page::PageFrontMatter;
+
+    fn test_config() -> Config {
+        Config {
+            base_url: "https://example.com".to_string(),
+            default_language: "en".to_string(),
+            rss_limit: 20,
+            taxonomies: vec![],
+        }
+    }
+
+    #[test]
+    fn splits_into_the_right_number_of_pagers_with_prev_next_links() {
+        let pages: Vec<Page> = (0..5)
+            .map(|i| Page { file_path: format!("{}.md", i), permalink: String::new(), meta: PageFrontMatter::default() })
+            .collect();
+        let refs: Vec<&Page> = pages.iter().collect();
+        let config = test_config();
+
+        let paginator = Paginator::from_pages(&refs, 2, "tags/rust", &config);
+
+        assert_eq!(paginator.pagers.len(), 3);
+        assert_eq!(paginator.pagers[0].path, "tags/rust/");
+        assert_eq!(paginator.pagers[1].path, "tags/rust/page/2/");
+        assert_eq!(paginator.pagers[0].previous, None);
+        assert_eq!(paginator.pagers[0].next, Some(paginator.pagers[1].permalink.clone()));
+        assert_eq!(paginator.pagers[2].next, None);
+    }
+
+    #[test]
+    fn pager_exposes_the_field_names_templates_rely_on() {
+        let pages: Vec<Page> = (0..3)
+            .map(|i| Page { file_path: format!("{}.md", i), permalink: String::new(), meta: PageFrontMatter::default() })
+            .collect();
+        let refs: Vec<&Page> = pages.iter().collect();
+        let config = test_config();
+
+        let paginator = Paginator::from_pages(&refs, 2, "tags/rust", &config);
+
+        assert_eq!(paginator.pagers[0].current_index, 1);
+        assert_eq!(paginator.pagers[1].current_index, 2);
+        assert_eq!(paginator.pagers[0].number_of_pages, 2);
+        assert_eq!(paginator.pagers[1].number_of_pages, 2);
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::NaiveDate;
+
+use content::Page;
+
+/// Parses a page's `date` front-matter field, which is expected to be ISO-8601
+/// (`YYYY-MM-DD`). Returns `None` for anything else so such pages are treated the
+/// same as pages missing a date, rather than sorting on the raw string.
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// How to sort the pages of a section or taxonomy term.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    Date,
+    Weight,
+    Title,
+    None,
+}
+
+impl Default for SortBy {
+    fn default() -> Self {
+        SortBy::Date
+    }
+}
+
+/// Sorts `pages` by `sort_by`, returning the sorted pages and, separately, the pages
+/// that couldn't be sorted because they are missing the field being sorted on
+/// (eg. no `date` set while sorting by date).
+pub fn sort_pages<'a>(pages: Vec<&'a Page>, sort_by: SortBy) -> (Vec<&'a Page>, Vec<&'a Page>) {
+    if sort_by == SortBy::None {
+        return (pages, vec![]);
+    }
+
+    let (mut can_be_sorted, cannot_be_sorted): (Vec<_>, Vec<_>) = pages.into_iter().partition(|page| {
+        match sort_by {
+            SortBy::Date => page.meta.date.as_ref().and_then(|d| parse_date(d)).is_some(),
+            SortBy::Weight => page.meta.weight.is_some(),
+            SortBy::Title => true,
+            SortBy::None => unreachable!(),
+        }
+    });
+
+    match sort_by {
+        SortBy::Date => can_be_sorted.sort_by(|a, b| {
+            let a_date = a.meta.date.as_ref().and_then(|d| parse_date(d));
+            let b_date = b.meta.date.as_ref().and_then(|d| parse_date(d));
+            b_date.cmp(&a_date)
+        }),
+        SortBy::Weight => can_be_sorted.sort_by(|a, b| a.meta.weight.cmp(&b.meta.weight)),
+        SortBy::Title => can_be_sorted.sort_by(|a, b| a.meta.title.cmp(&b.meta.title)),
+        SortBy::None => unreachable!(),
+    }
+
+    (can_be_sorted, cannot_be_sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use content::page::PageFrontMatter;
+
+    fn page_with_date(date: &str) -> Page {
+        Page {
+            file_path: format!("{}.md", date),
+            permalink: String::new(),
+            meta: PageFrontMatter { date: Some(date.to_string()), ..PageFrontMatter::default() },
+        }
+    }
+
+    #[test]
+    fn sorts_by_date_most_recent_first_and_isolates_undated_pages() {
+        let dated = page_with_date("2018-01-01");
+        let more_recent = page_with_date("2018-06-01");
+        let undated = Page {
+            file_path: "undated.md".to_string(),
+            permalink: String::new(),
+            meta: PageFrontMatter::default(),
+        };
+        let pages = vec![&dated, &undated, &more_recent];
+
+        let (sorted, cannot_be_sorted) = sort_pages(pages, SortBy::Date);
+
+        assert_eq!(sorted, vec![&more_recent, &dated]);
+        assert_eq!(cannot_be_sorted, vec![&undated]);
+    }
+
+    #[test]
+    fn compares_parsed_dates_instead_of_lexicographic_strings() {
+        // "2018-9-1" sorts after "2018-10-01" lexicographically but is the earlier date
+        // once actually parsed; a malformed date is treated like a missing one.
+        let september = page_with_date("2018-9-1");
+        let october = page_with_date("2018-10-01");
+        let garbage = page_with_date("not-a-date");
+        let pages = vec![&october, &garbage, &september];
+
+        let (sorted, cannot_be_sorted) = sort_pages(pages, SortBy::Date);
+
+        assert_eq!(sorted, vec![&october, &september]);
+        assert_eq!(cannot_be_sorted, vec![&garbage]);
+    }
+}
@@ -3,27 +3,23 @@ use std::collections::HashMap;
 use slug::slugify;
 use tera::{Context, Tera};
 
-use config::Config;
+use config::{Config, Taxonomy as TaxonomyConfig};
 use errors::{Result, ResultExt};
 use content::Page;
+use content::sorting::{sort_pages, SortBy};
+use content::pagination::Paginator;
 
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum TaxonomyKind {
-    Tags,
-    Categories,
-}
-
-/// A tag or category
+/// A single term of a taxonomy, eg. a specific tag or category, and the pages tagged with it
 #[derive(Debug, Clone, Serialize, PartialEq)]
-pub struct TaxonomyItem {
+pub struct TaxonomyItem<'a> {
     pub name: String,
     pub slug: String,
-    pub pages: Vec<Page>,
+    pub pages: Vec<&'a Page>,
 }
 
-impl TaxonomyItem {
-    pub fn new(name: &str, pages: Vec<Page>) -> TaxonomyItem {
+impl<'a> TaxonomyItem<'a> {
+    pub fn new(name: &str, pages: Vec<&'a Page>) -> TaxonomyItem<'a> {
         TaxonomyItem {
             name: name.to_string(),
             slug: slugify(name),
@@ -32,59 +28,89 @@ impl TaxonomyItem {
     }
 }
 
-/// All the tags or categories
+/// All the items belonging to a user-defined taxonomy, eg. all the tags and
+/// the pages tagged with each of them
 #[derive(Debug, Clone, PartialEq)]
-pub struct Taxonomy {
-    pub kind: TaxonomyKind,
+pub struct Taxonomy<'a> {
+    pub kind: TaxonomyConfig,
     // this vec is sorted by the count of item
-    pub items: Vec<TaxonomyItem>,
+    pub items: Vec<TaxonomyItem<'a>>,
 }
 
-impl Taxonomy {
-    // TODO: take a Vec<&'a Page> if it makes a difference in terms of perf for actual sites
-    pub fn find_tags_and_categories(all_pages: Vec<Page>) -> (Taxonomy, Taxonomy) {
-        let mut tags = HashMap::new();
-        let mut categories = HashMap::new();
-
-        // Find all the tags/categories first
-        for page in all_pages {
-            if let Some(ref category) = page.meta.category {
-                categories
-                    .entry(category.to_string())
-                    .or_insert_with(|| vec![])
-                    .push(page.clone());
-            }
+impl<'a> Taxonomy<'a> {
+    fn new(kind: TaxonomyConfig, items: HashMap<String, Vec<&'a Page>>) -> Result<Taxonomy<'a>> {
+        let mut sorted_items = vec![];
+        for (name, pages) in items {
+            // Pages missing the sort field (eg. no `date`) are kept, just pushed to the back.
+            let (mut pages, unsortable) = sort_pages(pages, kind.sort_by);
+            pages.extend(unsortable);
+            sorted_items.push(
+                TaxonomyItem::new(&name, pages)
+            );
+        }
+        sorted_items.sort_by(|a, b| b.pages.len().cmp(&a.pages.len()));
 
-            if let Some(ref t) = page.meta.tags {
-                for tag in t {
-                    tags
-                        .entry(tag.to_string())
-                        .or_insert_with(|| vec![])
-                        .push(page.clone());
+        // Two distinct term names can slugify to the same value (eg. "C++" and "C"),
+        // which would otherwise silently overwrite each other's output directory.
+        let mut slugs_seen: HashMap<String, String> = HashMap::new();
+        for item in &mut sorted_items {
+            if let Some(previous_name) = slugs_seen.get(&item.slug).cloned() {
+                if kind.rename_conflicting_slugs {
+                    let base_slug = item.slug.clone();
+                    let mut count = 1;
+                    while slugs_seen.contains_key(&format!("{}-{}", base_slug, count)) {
+                        count += 1;
+                    }
+                    item.slug = format!("{}-{}", base_slug, count);
+                } else {
+                    bail!(
+                        "Taxonomy `{}` has two terms (`{}` and `{}`) slugifying to the same `{}`: \
+                        rename one of them or set `rename_conflicting_slugs = true` in config.toml",
+                        kind.name, previous_name, item.name, item.slug
+                    );
                 }
             }
+            slugs_seen.insert(item.slug.clone(), item.name.clone());
         }
 
-        // Then make TaxonomyItem out of them, after sorting it
-        let tags_taxonomy = Taxonomy::new(TaxonomyKind::Tags, tags);
-        let categories_taxonomy = Taxonomy::new(TaxonomyKind::Categories, categories);
-
-        (tags_taxonomy, categories_taxonomy)
+        Ok(Taxonomy {
+            kind,
+            items: sorted_items,
+        })
     }
 
-    fn new(kind: TaxonomyKind, items: HashMap<String, Vec<Page>>) -> Taxonomy {
-        let mut sorted_items = vec![];
-        for (name, pages) in &items {
-            sorted_items.push(
-                TaxonomyItem::new(name, pages.clone())
-            );
+    /// Finds all the taxonomies defined in `config.toml` and groups the pages belonging
+    /// to each of their terms together.
+    pub fn find_taxonomies(all_pages: &'a [Page], config: &Config) -> Result<Vec<Taxonomy<'a>>> {
+        // taxonomy name -> (term -> pages)
+        let mut terms_by_taxonomy: HashMap<String, HashMap<String, Vec<&'a Page>>> = HashMap::new();
+        for taxonomy in &config.taxonomies {
+            terms_by_taxonomy.insert(taxonomy.name.clone(), HashMap::new());
         }
-        sorted_items.sort_by(|a, b| b.pages.len().cmp(&a.pages.len()));
 
-        Taxonomy {
-            kind,
-            items: sorted_items,
+        for page in all_pages {
+            for (taxonomy_name, terms) in &page.meta.taxonomies {
+                let terms_for_taxonomy = match terms_by_taxonomy.get_mut(taxonomy_name) {
+                    Some(t) => t,
+                    None => continue,
+                };
+
+                for term in terms {
+                    terms_for_taxonomy
+                        .entry(term.to_string())
+                        .or_insert_with(|| vec![])
+                        .push(page);
+                }
+            }
         }
+
+        config.taxonomies
+            .iter()
+            .map(|taxonomy| {
+                let items = terms_by_taxonomy.remove(&taxonomy.name).unwrap_or_default();
+                Taxonomy::new(taxonomy.clone(), items)
+            })
+            .collect::<Result<Vec<_>>>()
     }
 
     pub fn len(&self) -> usize {
@@ -92,16 +118,30 @@ impl Taxonomy {
     }
 
     pub fn get_single_item_name(&self) -> String {
-        match self.kind {
-            TaxonomyKind::Tags => "tag".to_string(),
-            TaxonomyKind::Categories => "category".to_string(),
-        }
+        self.kind.name.clone()
     }
 
     pub fn get_list_name(&self) -> String {
-        match self.kind {
-            TaxonomyKind::Tags => "tags".to_string(),
-            TaxonomyKind::Categories => "categories".to_string(),
+        self.kind.name.clone()
+    }
+
+    /// `{taxonomy_name}/single.html`, falling back to `taxonomy_single.html`
+    fn single_item_template(&self, tera: &Tera) -> String {
+        let specific = format!("{}/single.html", self.get_single_item_name());
+        if tera.templates.contains_key(&specific) {
+            specific
+        } else {
+            "taxonomy_single.html".to_string()
+        }
+    }
+
+    /// `{taxonomy_name}/list.html`, falling back to `taxonomy_list.html`
+    fn list_template(&self, tera: &Tera) -> String {
+        let specific = format!("{}/list.html", self.get_list_name());
+        if tera.templates.contains_key(&specific) {
+            specific
+        } else {
+            "taxonomy_list.html".to_string()
         }
     }
 
@@ -109,27 +149,194 @@ impl Taxonomy {
         let name = self.get_single_item_name();
         let mut context = Context::new();
         context.add("config", config);
-        // TODO: how to sort categories and tag content?
-        // Have a setting in config.toml or a _category.md and _tag.md
-        // The latter is more in line with the rest of Gutenberg but order ordering
-        // doesn't really work across sections.
         context.add(&name, item);
         context.add("current_url", &config.make_permalink(&format!("{}/{}", name, item.slug)));
         context.add("current_path", &format!("/{}/{}", name, item.slug));
 
-        tera.render(&format!("{}.html", name), &context)
-            .chain_err(|| format!("Failed to render {} page.", name))
+        let template = self.single_item_template(tera);
+        tera.render(&template, &context)
+            .chain_err(|| format!("Failed to render single item of {} taxonomy.", name))
     }
 
-    pub fn render_list(&self, tera: &Tera, config: &Config) -> Result<String> {
-        let name = self.get_list_name();
+    /// Same as `render_single_item` but splits `item.pages` into several pages of
+    /// `paginate_by` pages each, mirroring how sections are paginated. Returns one
+    /// rendered page per `Paginator` page when pagination is enabled for this taxonomy,
+    /// or a single rendered page otherwise.
+    pub fn render_paginated_item(&self, item: &TaxonomyItem, tera: &Tera, config: &Config) -> Result<Vec<String>> {
+        let paginate_by = match self.kind.paginate_by {
+            Some(n) if n > 0 => n,
+            _ => return Ok(vec![self.render_single_item(item, tera, config)?]),
+        };
+
+        let name = self.get_single_item_name();
+        let base_path = format!("{}/{}", name, item.slug);
+        let paginator = Paginator::from_pages(&item.pages, paginate_by, &base_path, config);
+        let template = self.single_item_template(tera);
+
+        paginator.pagers
+            .iter()
+            .map(|pager| {
+                let mut context = Context::new();
+                context.add("config", config);
+                context.add(&name, item);
+                context.add("paginator", pager);
+                context.add("current_url", &pager.permalink);
+                context.add("current_path", &pager.path);
+
+                tera.render(&template, &context)
+                    .chain_err(|| format!("Failed to render paginated page of {} taxonomy.", name))
+            })
+            .collect()
+    }
+
+    /// Renders an Atom feed for a single term, eg. `/tags/rust/atom.xml`, when `rss` is
+    /// enabled for this taxonomy. Returns `None` when disabled so callers can skip
+    /// writing the file altogether.
+    pub fn render_feed(&self, item: &TaxonomyItem, tera: &Tera, config: &Config) -> Result<Option<String>> {
+        if !self.kind.rss {
+            return Ok(None);
+        }
+
+        let name = self.get_single_item_name();
+        // A feed must be most-recent-first regardless of how the term page itself is
+        // sorted (eg. `sort_by = "weight"`), so re-sort by date here rather than reusing
+        // `item.pages`'s order.
+        let (mut by_date, undated) = sort_pages(item.pages.clone(), SortBy::Date);
+        by_date.extend(undated);
+        let pages: Vec<_> = by_date.into_iter().take(config.rss_limit).collect();
+
         let mut context = Context::new();
         context.add("config", config);
-        context.add(&name, &self.items);
-        context.add("current_url", &config.make_permalink(&name));
-        context.add("current_path", &name);
+        context.add("pages", &pages);
+        context.add("lang", &config.default_language);
+        context.add(
+            "feed_url",
+            &config.make_permalink(&format!("{}/{}/atom.xml", name, item.slug)),
+        );
+
+        tera.render("atom.xml", &context)
+            .chain_err(|| format!("Failed to render feed for term `{}` in the {} taxonomy.", item.name, name))
+            .map(Some)
+    }
+
+    /// Renders the list of terms, split into `paginate_by` terms per page when set,
+    /// mirroring `render_paginated_item`.
+    pub fn render_list(&self, tera: &Tera, config: &Config) -> Result<Vec<String>> {
+        let name = self.get_list_name();
+        let template = self.list_template(tera);
+
+        let paginate_by = self.kind.paginate_by.filter(|&n| n > 0);
+        let chunks: Vec<&[TaxonomyItem]> = match paginate_by {
+            Some(n) => self.items.chunks(n).collect(),
+            None => vec![&self.items[..]],
+        };
+
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                let index = i + 1;
+                let path = if index == 1 { name.clone() } else { format!("{}/page/{}", name, index) };
+
+                let mut context = Context::new();
+                context.add("config", config);
+                context.add(&name, chunk);
+                context.add("current_url", &config.make_permalink(&path));
+                context.add("current_path", &format!("/{}", path));
+
+                tera.render(&template, &context)
+                    .chain_err(|| format!("Failed to render list page of {} taxonomy.", name))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use content::page::PageFrontMatter;
+
+    fn test_config() -> Config {
+        Config {
+            base_url: "https://example.com".to_string(),
+            default_language: "en".to_string(),
+            rss_limit: 20,
+            taxonomies: vec![],
+        }
+    }
 
-        tera.render(&format!("{}.html", name), &context)
-            .chain_err(|| format!("Failed to render {} page.", name))
+    fn tags_config(rss: bool) -> TaxonomyConfig {
+        TaxonomyConfig {
+            name: "tags".to_string(),
+            // deliberately not `Date`, to prove the feed doesn't just reuse this order
+            sort_by: SortBy::Weight,
+            paginate_by: None,
+            rss,
+            rename_conflicting_slugs: false,
+        }
+    }
+
+    fn page_with(date: &str, weight: usize) -> Page {
+        Page {
+            file_path: format!("{}.md", weight),
+            permalink: String::new(),
+            meta: PageFrontMatter { date: Some(date.to_string()), weight: Some(weight), ..PageFrontMatter::default() },
+        }
+    }
+
+    #[test]
+    fn render_feed_returns_none_when_rss_disabled() {
+        let page = page_with("2018-01-01", 1);
+        let item = TaxonomyItem::new("rust", vec![&page]);
+        let taxonomy = Taxonomy { kind: tags_config(false), items: vec![] };
+        let tera = Tera::default();
+
+        assert_eq!(taxonomy.render_feed(&item, &tera, &test_config()).unwrap(), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn render_feed_sorts_by_date_and_respects_rss_limit_regardless_of_term_sort_by() {
+        let old = page_with("2018-01-01", 5);
+        let recent = page_with("2018-06-01", 1);
+        // `item.pages` is oldest-first here, the feed must still come out
+        // most-recent-first.
+        let item = TaxonomyItem::new("rust", vec![&old, &recent]);
+        let taxonomy = Taxonomy { kind: tags_config(true), items: vec![] };
+
+        let mut tera = Tera::default();
+        tera.add_raw_template("atom.xml", "{% for page in pages %}{{ page.file_path }};{% endfor %}").unwrap();
+        let mut config = test_config();
+        config.rss_limit = 1;
+
+        let feed = taxonomy.render_feed(&item, &tera, &config).unwrap().unwrap();
+        assert_eq!(feed, "1.md;");
+    }
+
+    #[test]
+    fn new_errors_on_slug_collision_without_rename_flag() {
+        let items: HashMap<String, Vec<&Page>> =
+            vec![("C".to_string(), vec![]), ("C++".to_string(), vec![])].into_iter().collect();
+
+        assert!(Taxonomy::new(tags_config(false), items).is_err());
+    }
+
+    #[test]
+    fn new_disambiguates_every_colliding_slug_when_rename_flag_is_set() {
+        let mut kind = tags_config(false);
+        kind.rename_conflicting_slugs = true;
+        // "C", "C++" and "C#" all slugify to "c"
+        let items: HashMap<String, Vec<&Page>> = vec![
+            ("C".to_string(), vec![]),
+            ("C++".to_string(), vec![]),
+            ("C#".to_string(), vec![]),
+        ].into_iter().collect();
+
+        let taxonomy = Taxonomy::new(kind, items).unwrap();
+
+        let mut slugs: Vec<_> = taxonomy.items.iter().map(|i| i.slug.clone()).collect();
+        let before_dedup = slugs.len();
+        slugs.sort();
+        slugs.dedup();
+        assert_eq!(slugs.len(), before_dedup, "every term must end up with a unique slug");
+    }
+}
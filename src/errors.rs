@@ -0,0 +1,5 @@
+error_chain! {
+    foreign_links {
+        Io(::std::io::Error);
+    }
+}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate error_chain;
+#[macro_use]
+extern crate serde_derive;
+extern crate chrono;
+extern crate slug;
+extern crate tera;
+
+pub mod config;
+pub mod content;
+pub mod errors;
+pub mod site;
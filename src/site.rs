@@ -0,0 +1,70 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use config::Config;
+use content::{Page, Taxonomy};
+use errors::{Result, ResultExt};
+use tera::Tera;
+
+/// Owns the pages for a build and drives rendering, including the taxonomy pages.
+pub struct Site {
+    pub config: Config,
+    pub tera: Tera,
+    pub pages: Vec<Page>,
+    pub output_path: PathBuf,
+}
+
+impl Site {
+    /// Builds every configured taxonomy from `self.pages` and writes out their term,
+    /// list, pagination and feed pages.
+    pub fn render_taxonomies(&self) -> Result<()> {
+        let taxonomies = Taxonomy::find_taxonomies(&self.pages, &self.config)?;
+
+        for taxonomy in &taxonomies {
+            let name = taxonomy.get_list_name();
+
+            for (i, page) in taxonomy.render_list(&self.tera, &self.config)?.into_iter().enumerate() {
+                let path = if i == 0 {
+                    vec![name.clone(), "index.html".to_string()]
+                } else {
+                    vec![name.clone(), "page".to_string(), (i + 1).to_string(), "index.html".to_string()]
+                };
+                self.write_html(&path.iter().map(|s| s.as_str()).collect::<Vec<_>>(), &page)?;
+            }
+
+            for item in &taxonomy.items {
+                for (i, page) in taxonomy.render_paginated_item(item, &self.tera, &self.config)?.into_iter().enumerate() {
+                    let path = if i == 0 {
+                        vec![name.clone(), item.slug.clone(), "index.html".to_string()]
+                    } else {
+                        vec![name.clone(), item.slug.clone(), "page".to_string(), (i + 1).to_string(), "index.html".to_string()]
+                    };
+                    self.write_html(&path.iter().map(|s| s.as_str()).collect::<Vec<_>>(), &page)?;
+                }
+
+                if let Some(feed) = taxonomy.render_feed(item, &self.tera, &self.config)? {
+                    self.write_html(&[&name, &item.slug, "atom.xml"], &feed)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_html(&self, components: &[&str], content: &str) -> Result<()> {
+        let mut path = self.output_path.clone();
+        for component in components {
+            path.push(component);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .chain_err(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+
+        File::create(&path)
+            .and_then(|mut f| f.write_all(content.as_bytes()))
+            .chain_err(|| format!("Failed to write {}", path.display()))
+    }
+}